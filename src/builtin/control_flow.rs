@@ -1,17 +1,92 @@
 use crate::prelude::*;
 
 pub fn sh_flow(node: Node, shenv: &mut ShEnv, kind: ShErrKind) -> ShResult<()> {
+	let blame = node.span();
 	let rule = node.into_rule();
-	let mut code: i32 = 0;
-	if let NdRule::Command { argv, redirs } = rule {
-		let mut argv_iter = argv.into_iter();
-		while let Some(arg) = argv_iter.next() {
-			if let Ok(code_arg) = shenv.input_slice(arg.span()).parse() {
-				code = code_arg
+	let cmd = match kind {
+		ShErrKind::CleanExit => "exit",
+		ShErrKind::FuncReturn => "return",
+		ShErrKind::LoopBreak(_) => "break",
+		ShErrKind::LoopContinue(_) => "continue",
+		_ => unreachable!()
+	};
+	let mut arg: Option<i32> = None;
+	if let NdRule::Command { argv, redirs: _ } = rule {
+		let mut argv_iter = argv.into_iter().skip(1);
+		if let Some(token) = argv_iter.next() {
+			let raw = token.as_raw(shenv);
+			match raw.parse::<i32>() {
+				Ok(num) => arg = Some(num),
+				Err(_) => {
+					return Err(
+						ShErr::full(
+							ShErrKind::ExecFail,
+							format!("{}: numeric argument required",cmd),
+							shenv.get_input(),
+							token.span()
+						)
+					)
+				}
 			}
 		}
 	} else { unreachable!() }
-	shenv.set_code(code);
+
+	let kind = match kind {
+		ShErrKind::LoopBreak(_) | ShErrKind::LoopContinue(_) => {
+			let loop_depth = shenv.ctx().loop_depth();
+			if loop_depth == 0 {
+				return Err(
+					ShErr::full(
+						ShErrKind::ExecFail,
+						format!("{}: only meaningful in a loop",cmd),
+						shenv.get_input(),
+						blame
+					)
+				)
+			}
+			let level = arg.unwrap_or(1);
+			if level < 1 {
+				return Err(
+					ShErr::full(
+						ShErrKind::ExecFail,
+						format!("{}: loop count out of range",cmd),
+						shenv.get_input(),
+						blame
+					)
+				)
+			}
+			// Breaking out of more loops than exist just leaves all of them
+			let level = (level as usize).min(loop_depth);
+			shenv.set_code(0);
+			match kind {
+				ShErrKind::LoopBreak(_) => ShErrKind::LoopBreak(level),
+				_ => ShErrKind::LoopContinue(level)
+			}
+		}
+		ShErrKind::FuncReturn => {
+			let flags = shenv.ctx().flags();
+			if !flags.intersects(ExecFlags::IN_FUNC | ExecFlags::IN_SOURCE) {
+				return Err(
+					ShErr::full(
+						ShErrKind::ExecFail,
+						"return: can only return from a function or sourced file",
+						shenv.get_input(),
+						blame
+					)
+				)
+			}
+			if let Some(code) = arg {
+				shenv.set_code(code);
+			}
+			kind
+		}
+		_ => {
+			if let Some(code) = arg {
+				shenv.set_code(code);
+			}
+			kind
+		}
+	};
 	// Our control flow keywords are used as ShErrKinds
 	// This design will halt the execution flow and start heading straight back upward
 	// Function returns and loop breaks/continues will be caught in the proper context to allow
@@ -31,6 +31,10 @@ pub fn exec_input<S: Into<String>>(input: S, shenv: &mut ShEnv) -> ShResult<()>
 	if let Err(e) = Executor::new(syn_tree, shenv).walk() {
 		if let ShErrKind::CleanExit = e.kind() {
 			let code = shenv.get_code();
+			if shenv.ctx().flags().contains(ExecFlags::IN_SUBSH) {
+				// Only leave the subshell, not the parent shell
+				exit(code);
+			}
 			sh_quit(code);
 		} else {
 			if !shenv.ctx().flags().contains(ExecFlags::IN_FUNC) {
@@ -104,7 +108,8 @@ fn dispatch_node(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 		NdRule::Assignment {..} => dispatch_command(node, shenv).try_blame(node_raw, span)?,
 		NdRule::IfThen {..} => shellcmd::exec_if(node, shenv).try_blame(node_raw, span)?,
 		NdRule::Loop {..} => shellcmd::exec_loop(node, shenv).try_blame(node_raw, span)?,
-		NdRule::ForLoop {..} => shellcmd::exec_for(node, shenv).try_blame(node_raw, span)?,
+		NdRule::ForLoop {..} |
+		NdRule::ArithFor {..} => shellcmd::exec_for(node, shenv).try_blame(node_raw, span)?,
 		NdRule::Case {..} => shellcmd::exec_case(node, shenv).try_blame(node_raw, span)?,
 		NdRule::FuncDef {..} => exec_funcdef(node,shenv).try_blame(node_raw, span)?,
 		NdRule::Pipeline {..} => exec_pipeline(node, shenv).try_blame(node_raw, span)?,
@@ -159,6 +164,7 @@ fn exec_func(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 		let body = shenv.logic().get_function(&func_name).unwrap().to_string();
 		let snapshot = shenv.clone();
 		shenv.vars_mut().reset_params();
+		shenv.vars_mut().reset_pos_params();
 		shenv.ctx_mut().set_flag(ExecFlags::IN_FUNC);
 		while let Some(arg) = argv_iter.next() {
 			let arg_raw = shenv.input_slice(arg.span()).to_string();
@@ -207,6 +213,7 @@ fn exec_subshell(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	if let NdRule::Subshell { body, argv, redirs } = rule {
 		if shenv.ctx().flags().contains(ExecFlags::NO_FORK) {
 			shenv.ctx_mut().unset_flag(ExecFlags::NO_FORK); // Allow sub-forks in this case
+			shenv.ctx_mut().set_flag(ExecFlags::IN_SUBSH);
 			shenv.collect_redirs(redirs);
 			if let Err(e) = shenv.ctx_mut().activate_rdrs() {
 				write_err(e)?;
@@ -228,6 +235,7 @@ fn exec_subshell(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 		} else {
 			match unsafe { fork()? } {
 				Child => {
+					shenv.ctx_mut().set_flag(ExecFlags::IN_SUBSH);
 					shenv.collect_redirs(redirs);
 					if let Err(e) = shenv.ctx_mut().activate_rdrs() {
 						write_err(e)?;
@@ -282,8 +290,8 @@ fn exec_builtin(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 		"alias" => alias(node, shenv)?,
 		"exit" => sh_flow(node, shenv, ShErrKind::CleanExit)?,
 		"return" => sh_flow(node, shenv, ShErrKind::FuncReturn)?,
-		"break" => sh_flow(node, shenv, ShErrKind::LoopBreak)?,
-		"continue" => sh_flow(node, shenv, ShErrKind::LoopContinue)?,
+		"break" => sh_flow(node, shenv, ShErrKind::LoopBreak(1))?,
+		"continue" => sh_flow(node, shenv, ShErrKind::LoopContinue(1))?,
 		"source" => source(node, shenv)?,
 		_ => unimplemented!("Have not yet implemented support for builtin `{}'",command)
 	}
@@ -381,7 +389,7 @@ fn exec_pipeline(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 			match unsafe { fork()? } {
 				Child => {
 					// Set NO_FORK since we are already in a fork, to prevent unnecessarily forking again
-					shenv.ctx_mut().set_flag(ExecFlags::NO_FORK);
+					shenv.ctx_mut().set_flag(ExecFlags::NO_FORK | ExecFlags::IN_SUBSH);
 					// We close this r_pipe since it's the one the next command will use, so not useful here
 					if let Some(r_pipe) = r_pipe.take() {
 						close(r_pipe)?;
@@ -401,10 +409,13 @@ fn exec_pipeline(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 					}
 
 					if let Err(e) = dispatch_node(cmd, shenv) {
+						if e.kind() == ShErrKind::CleanExit {
+							exit(shenv.get_code());
+						}
 						eprintln!("{}",e);
 						exit(1);
 					}
-					exit(0);
+					exit(shenv.get_code());
 				}
 				Parent { child } => {
 					// Close the write pipe out here to signal EOF
@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{expand::arithmetic::eval_arith_clause, prelude::*};
 
 pub fn exec_if(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	let rule = node.into_rule();
@@ -26,6 +26,24 @@ pub fn exec_if(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	Ok(())
 }
 
+/// Handles loop control flow coming out of a loop body
+/// Returns Ok(true) if the loop should keep going, and Ok(false) if it should stop
+/// Breaks and continues that target an outer loop are passed upward with their level decremented
+fn loop_flow(result: ShResult<i32>) -> ShResult<bool> {
+	match result {
+		Ok(_) => Ok(true),
+		Err(e) => {
+			match e.kind() {
+				ShErrKind::LoopContinue(1) => Ok(true),
+				ShErrKind::LoopBreak(1) => Ok(false),
+				ShErrKind::LoopContinue(level) => Err(ShErr::simple(ShErrKind::LoopContinue(level - 1), "")),
+				ShErrKind::LoopBreak(level) => Err(ShErr::simple(ShErrKind::LoopBreak(level - 1), "")),
+				_ => Err(e)
+			}
+		}
+	}
+}
+
 pub fn exec_loop(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	let rule = node.into_rule();
 
@@ -36,43 +54,28 @@ pub fn exec_loop(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 			shenv.ctx_mut().unset_flag(ExecFlags::NO_FORK);
 		}
 
-		loop {
-			let ret = shenv.exec_as_cond(cond.clone())?;
-			match kind {
-				LoopKind::While => {
-					if ret == 0 {
-						match shenv.exec_as_body(body.clone()) {
-							Ok(_) => continue,
-							Err(e) => {
-								match e.kind() {
-									ShErrKind::LoopContinue => continue,
-									ShErrKind::LoopBreak => break,
-									_ => return Err(e.into())
-								}
-							}
-						}
-					} else { break }
-				}
-				LoopKind::Until => {
-					if ret != 0 {
-						match shenv.exec_as_body(body.clone()) {
-							Ok(_) => continue,
-							Err(e) => {
-								match e.kind() {
-									ShErrKind::LoopContinue => continue,
-									ShErrKind::LoopBreak => break,
-									_ => return Err(e.into())
-								}
-							}
-						}
-					} else { break }
-				}
-			}
-		}
+		shenv.ctx_mut().enter_loop();
+		let result = run_loop(kind, cond, body, shenv);
+		shenv.ctx_mut().exit_loop();
+		result?
 	} else { unreachable!() }
 	Ok(())
 }
 
+fn run_loop(kind: LoopKind, cond: Vec<Node>, body: Vec<Node>, shenv: &mut ShEnv) -> ShResult<()> {
+	loop {
+		let ret = shenv.exec_as_cond(cond.clone())?;
+		let keep_going = match kind {
+			LoopKind::While => ret == 0,
+			LoopKind::Until => ret != 0
+		};
+		if !keep_going || !loop_flow(shenv.exec_as_body(body.clone()))? {
+			break
+		}
+	}
+	Ok(())
+}
+
 pub fn exec_for(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	let rule = node.into_rule();
 
@@ -86,33 +89,74 @@ pub fn exec_for(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 		log!(DEBUG, vars);
 		log!(DEBUG, arr);
 
-		for chunk in arr.chunks(vars.len()) {
-			log!(DEBUG, "input: {}", shenv.get_input());
-			for (var,value) in vars.iter().zip(chunk.iter()) {
-				let var = var.as_raw(shenv);
-				let val = value.as_raw(shenv);
-				log!(DEBUG,var);
-				log!(DEBUG,val);
-				shenv.vars_mut().set_var(&var, &val);
-			}
+		// `for name; do` iterates over the positional parameters
+		let arr = match arr {
+			Some(arr) => arr.iter().map(|tk| tk.as_raw(shenv)).collect::<Vec<_>>(),
+			None => shenv.vars().pos_args()
+		};
 
-			if chunk.len() < vars.len() {
-				for var in &vars[chunk.len()..] { // If 'vars' is longer than the chunk, then unset the orphaned vars
-					let var = var.as_raw(shenv);
-					log!(DEBUG, "unsetting");
-					log!(DEBUG, var);
-					shenv.vars_mut().unset_var(&var);
-				}
-			}
+		shenv.ctx_mut().enter_loop();
+		let result = run_for(vars, arr, body, shenv);
+		shenv.ctx_mut().exit_loop();
+		*shenv.vars_mut() = saved_vars;
+		result?
+
+	} else if let NdRule::ArithFor { init, cond, step, body, redirs } = rule {
+		shenv.collect_redirs(redirs);
 
-			shenv.exec_as_body(body.clone())?;
+		if shenv.ctx().flags().contains(ExecFlags::NO_FORK) {
+			shenv.ctx_mut().unset_flag(ExecFlags::NO_FORK);
 		}
-		*shenv.vars_mut() = saved_vars;
 
+		shenv.ctx_mut().enter_loop();
+		let result = run_arith_for(&init, &cond, &step, body, shenv);
+		shenv.ctx_mut().exit_loop();
+		result?
 	} else { unreachable!() }
 	Ok(())
 }
 
+fn run_for(vars: Vec<Token>, arr: Vec<String>, body: Vec<Node>, shenv: &mut ShEnv) -> ShResult<()> {
+	for chunk in arr.chunks(vars.len()) {
+		log!(DEBUG, "input: {}", shenv.get_input());
+		for (var,val) in vars.iter().zip(chunk.iter()) {
+			let var = var.as_raw(shenv);
+			log!(DEBUG,var);
+			log!(DEBUG,val);
+			shenv.vars_mut().set_var(&var, val);
+		}
+
+		if chunk.len() < vars.len() {
+			for var in &vars[chunk.len()..] { // If 'vars' is longer than the chunk, then unset the orphaned vars
+				let var = var.as_raw(shenv);
+				log!(DEBUG, "unsetting");
+				log!(DEBUG, var);
+				shenv.vars_mut().unset_var(&var);
+			}
+		}
+
+		if !loop_flow(shenv.exec_as_body(body.clone()))? {
+			break
+		}
+	}
+	Ok(())
+}
+
+fn run_arith_for(init: &str, cond: &str, step: &str, body: Vec<Node>, shenv: &mut ShEnv) -> ShResult<()> {
+	eval_arith_clause(init, shenv)?;
+	loop {
+		// An empty condition is always true
+		if !cond.is_empty() && eval_arith_clause(cond, shenv)? == 0.0 {
+			break
+		}
+		if !loop_flow(shenv.exec_as_body(body.clone()))? {
+			break
+		}
+		eval_arith_clause(step, shenv)?;
+	}
+	Ok(())
+}
+
 pub fn exec_case(node: Node, shenv: &mut ShEnv) -> ShResult<()> {
 	let rule = node.into_rule();
 
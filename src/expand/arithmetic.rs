@@ -18,15 +18,27 @@ pub enum Op {
 	Div,
 	IntDiv,
 	Mod,
-	Pow
+	Pow,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	Eq,
+	Ne,
+	And,
+	Or
 }
 
 impl Op {
 	pub fn precedence(&self) -> u8 {
 		match self {
-			Op::Add | Op::Sub => 1,
-			Op::Mul | Op::Div | Op::IntDiv | Op::Mod => 2,
-			Op::Pow => 3
+			Op::Or => 1,
+			Op::And => 2,
+			Op::Eq | Op::Ne => 3,
+			Op::Lt | Op::Le | Op::Gt | Op::Ge => 4,
+			Op::Add | Op::Sub => 5,
+			Op::Mul | Op::Div | Op::IntDiv | Op::Mod => 6,
+			Op::Pow => 7
 		}
 	}
 	pub fn is_left_associative(&self) -> bool {
@@ -41,7 +53,24 @@ fn tokenize_expr(expr: &str) -> ShResult<Vec<ExprToken>> {
 	while let Some(ch) = chars.next() {
 		match ch {
 			'+' => tokens.push(ExprToken::Operator(Op::Add)),
-			'-' => tokens.push(ExprToken::Operator(Op::Sub)),
+			'-' => {
+				// A minus sign at the start of an operand is a negative number
+				let is_unary = matches!(tokens.last(), None | Some(ExprToken::Operator(_)) | Some(ExprToken::OpenParen));
+				if is_unary && chars.peek().is_some_and(|ch| ch.is_ascii_digit()) {
+					let mut number = ch.to_string();
+					while let Some(next_ch) = chars.peek() {
+						if next_ch.is_ascii_digit() || *next_ch == '.' {
+							number.push(chars.next().unwrap());
+						} else {
+							break;
+						}
+					}
+					let value = number.parse::<f64>().map_err(|_| ShErr::simple(ShErrKind::ParseErr, format!("Invalid number in arithmetic expansion: {}",number)))?;
+					tokens.push(ExprToken::Number(value));
+				} else {
+					tokens.push(ExprToken::Operator(Op::Sub))
+				}
+			}
 			'*' => {
 				if chars.peek() == Some(&'*') {
 					chars.next();
@@ -59,6 +88,38 @@ fn tokenize_expr(expr: &str) -> ShResult<Vec<ExprToken>> {
 				}
 			}
 			'%' => tokens.push(ExprToken::Operator(Op::Mod)),
+			'<' => {
+				if chars.peek() == Some(&'=') {
+					chars.next();
+					tokens.push(ExprToken::Operator(Op::Le));
+				} else {
+					tokens.push(ExprToken::Operator(Op::Lt));
+				}
+			}
+			'>' => {
+				if chars.peek() == Some(&'=') {
+					chars.next();
+					tokens.push(ExprToken::Operator(Op::Ge));
+				} else {
+					tokens.push(ExprToken::Operator(Op::Gt));
+				}
+			}
+			'=' if chars.peek() == Some(&'=') => {
+				chars.next();
+				tokens.push(ExprToken::Operator(Op::Eq));
+			}
+			'!' if chars.peek() == Some(&'=') => {
+				chars.next();
+				tokens.push(ExprToken::Operator(Op::Ne));
+			}
+			'&' if chars.peek() == Some(&'&') => {
+				chars.next();
+				tokens.push(ExprToken::Operator(Op::And));
+			}
+			'|' if chars.peek() == Some(&'|') => {
+				chars.next();
+				tokens.push(ExprToken::Operator(Op::Or));
+			}
 			'(' => tokens.push(ExprToken::OpenParen),
 			')' => tokens.push(ExprToken::CloseParen),
 			'0'..='9' => {
@@ -70,10 +131,21 @@ fn tokenize_expr(expr: &str) -> ShResult<Vec<ExprToken>> {
 						break;
 					}
 				}
+				// Allow for decimals, since values are stored as floats
+				if chars.peek() == Some(&'.') {
+					number.push(chars.next().unwrap());
+					while let Some(next_ch) = chars.peek() {
+						if next_ch.is_ascii_digit() {
+							number.push(chars.next().unwrap());
+						} else {
+							break;
+						}
+					}
+				}
 				let value = number.parse::<f64>().unwrap();
 				tokens.push(ExprToken::Number(value));
 			}
-			' ' | '\t' => continue, // Skip whitespace
+			' ' | '\t' | '\n' => continue, // Skip whitespace
 			_ => return Err(ShErr::simple(ShErrKind::ParseErr, format!("Unexpected character in arithmetic expansion: {}",ch))), // Handle unexpected characters
 		}
 	}
@@ -144,6 +216,14 @@ pub fn eval_rpn(tokens: Vec<ExprToken>) -> ShResult<f64> {
 					Op::Mul => lhs * rhs,
 					Op::Mod => lhs % rhs,
 					Op::Pow => lhs.powf(rhs),
+					Op::Lt => (lhs < rhs) as i32 as f64,
+					Op::Le => (lhs <= rhs) as i32 as f64,
+					Op::Gt => (lhs > rhs) as i32 as f64,
+					Op::Ge => (lhs >= rhs) as i32 as f64,
+					Op::Eq => (lhs == rhs) as i32 as f64,
+					Op::Ne => (lhs != rhs) as i32 as f64,
+					Op::And => (lhs != 0.0 && rhs != 0.0) as i32 as f64,
+					Op::Or => (lhs != 0.0 || rhs != 0.0) as i32 as f64,
 					Op::Div => {
 						if rhs == 0.0 {
 							return Err(ShErr::simple(ShErrKind::ParseErr, "Attempt to divide by zero in arithmetic expansion"))
@@ -185,8 +265,109 @@ pub fn expand_arith_string(s: &str,shenv: &mut ShEnv) -> ShResult<String> {
 	if exp.starts_with('`') && s.ends_with('`') {
 		exp = exp[1..exp.len() - 1].to_string();
 	}
-	let expr_tokens = shunting_yard(tokenize_expr(&exp)?)?;
+	let result = eval_arith_expr(&exp, shenv)?.to_string();
+	Ok(result)
+}
+
+/// Evaluate a single arithmetic expression with no side effects
+/// Bare identifiers are resolved to the value of the variable they name
+pub fn eval_arith_expr(expr: &str, shenv: &mut ShEnv) -> ShResult<f64> {
+	if expr.trim().is_empty() {
+		return Ok(0.0)
+	}
+	let expr = expand_arith_idents(expr, shenv);
+	let expr_tokens = shunting_yard(tokenize_expr(&expr)?)?;
 	log!(DEBUG,expr_tokens);
-	let result = eval_rpn(expr_tokens)?.to_string();
+	eval_rpn(expr_tokens)
+}
+
+/// Evaluate an arithmetic clause, like the ones found in `for ((init; cond; step))`
+/// A clause is a comma separated list of expressions, which may contain assignments
+/// like `i=0`, `i+=2`, `i++` and `--i`. The value of the last expression is returned.
+pub fn eval_arith_clause(clause: &str, shenv: &mut ShEnv) -> ShResult<f64> {
+	let clause = expand_string(clause, shenv)?;
+	let mut result = 0.0;
+	for expr in clause.split(',') {
+		result = eval_arith_assign(expr.trim(), shenv)?;
+	}
 	Ok(result)
 }
+
+fn eval_arith_assign(expr: &str, shenv: &mut ShEnv) -> ShResult<f64> {
+	let is_name = |s: &str| {
+		s.chars().next().is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_') &&
+		s.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+	};
+	let get_num = |name: &str, shenv: &ShEnv| {
+		shenv.vars().get_var(name).trim().parse::<f64>().unwrap_or(0.0)
+	};
+
+	// Increments and decrements
+	for (op,delta) in [("++",1.0),("--",-1.0)] {
+		if let Some(name) = expr.strip_suffix(op).map(str::trim) {
+			if is_name(name) {
+				let old = get_num(name, shenv);
+				shenv.vars_mut().set_var(name, &(old + delta).to_string());
+				return Ok(old)
+			}
+		}
+		if let Some(name) = expr.strip_prefix(op).map(str::trim) {
+			if is_name(name) {
+				let new = get_num(name, shenv) + delta;
+				shenv.vars_mut().set_var(name, &new.to_string());
+				return Ok(new)
+			}
+		}
+	}
+
+	// Assignments
+	if let Some(idx) = expr.find('=') {
+		let (lhs,rhs) = (&expr[..idx], &expr[idx + 1..]);
+		if !rhs.starts_with('=') {
+			let (name,op) = match lhs.trim_end().chars().last() {
+				Some(ch @ ('+' | '-' | '*' | '/' | '%')) => (lhs.trim_end()[..lhs.trim_end().len() - 1].trim(), Some(ch)),
+				_ => (lhs.trim(), None)
+			};
+			if is_name(name) {
+				let rhs = eval_arith_expr(rhs, shenv)?;
+				let new = match op {
+					Some(op) => {
+						let old = get_num(name, shenv);
+						let expr = format!("{old}{op}{rhs}");
+						eval_arith_expr(&expr, shenv)?
+					}
+					None => rhs
+				};
+				shenv.vars_mut().set_var(name, &new.to_string());
+				return Ok(new)
+			}
+		}
+	}
+
+	eval_arith_expr(expr, shenv)
+}
+
+/// Replace bare variable names in an arithmetic expression with their values
+/// Unset or non-numeric variables evaluate to zero
+fn expand_arith_idents(expr: &str, shenv: &ShEnv) -> String {
+	let mut result = String::new();
+	let mut chars = expr.chars().peekable();
+
+	while let Some(ch) = chars.next() {
+		if ch.is_ascii_alphabetic() || ch == '_' {
+			let mut name = ch.to_string();
+			while let Some(next_ch) = chars.peek() {
+				if next_ch.is_ascii_alphanumeric() || *next_ch == '_' {
+					name.push(chars.next().unwrap());
+				} else {
+					break
+				}
+			}
+			let value = shenv.vars().get_var(&name).trim().parse::<f64>().unwrap_or(0.0);
+			result.push_str(&value.to_string());
+		} else {
+			result.push(ch)
+		}
+	}
+	result
+}
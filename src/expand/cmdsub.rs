@@ -16,7 +16,7 @@ pub fn expand_cmdsub_string(mut s: &str, shenv: &mut ShEnv) -> ShResult<String>
 	let (r_pipe,w_pipe) = c_pipe()?;
 	let pipe_redir = Redir::output(1, w_pipe);
 	let mut sub_shenv = shenv.clone();
-	sub_shenv.ctx_mut().set_flag(ExecFlags::NO_FORK);
+	sub_shenv.ctx_mut().set_flag(ExecFlags::NO_FORK | ExecFlags::IN_SUBSH);
 	sub_shenv.collect_redirs(vec![pipe_redir]);
 
 	match unsafe { fork()? } {
@@ -117,8 +117,10 @@ pub enum ShErrKind {
 	CmdNotFound,
 	CleanExit,
 	FuncReturn,
-	LoopContinue,
-	LoopBreak,
+	/// Holds the number of enclosing loops to continue out of
+	LoopContinue(usize),
+	/// Holds the number of enclosing loops to break out of
+	LoopBreak(usize),
 	Null
 }
 
@@ -211,8 +213,8 @@ impl ShErr {
 						ShErrKind::CmdNotFound => "Command not found: ".into(),
 						ShErrKind::CleanExit |
 						ShErrKind::FuncReturn |
-						ShErrKind::LoopContinue |
-						ShErrKind::LoopBreak |
+						ShErrKind::LoopContinue(_) |
+						ShErrKind::LoopBreak(_) |
 						ShErrKind::Null => "".into()
 				}
 			}
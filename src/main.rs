@@ -59,9 +59,15 @@ fn parse_args(shenv: &mut ShEnv) {
 	let mut script_path: Option<PathBuf> = None;
 	let mut command: Option<String> = None;
 	let mut flags = FernFlags::empty();
+	let mut pos_args = vec![];
 
 	log!(DEBUG, args);
 	while let Some(mut arg) = args.next() {
+		// Everything after the script path or command string is a positional parameter
+		if script_path.is_some() || command.is_some() {
+			pos_args.push(arg);
+			continue
+		}
 		log!(DEBUG, arg);
 		if arg.starts_with("--") {
 			arg = arg.strip_prefix("--").unwrap().to_string();
@@ -79,11 +85,19 @@ fn parse_args(shenv: &mut ShEnv) {
 		} else {
 			let path_check = PathBuf::from(&arg);
 			if path_check.is_file() {
+				pos_args.push(arg);
 				script_path = Some(path_check);
 			}
 		}
 	}
 
+	if script_path.is_some() || command.is_some() {
+		if pos_args.is_empty() {
+			pos_args.push(std::env::args().next().unwrap_or_default());
+		}
+		shenv.vars_mut().set_pos_params(pos_args);
+	}
+
 	if !flags.contains(FernFlags::NO_RC) {
 		let _ = shenv.source_rc().eprint();
 	}
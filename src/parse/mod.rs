@@ -90,7 +90,8 @@ pub enum NdRule {
 	Case { pat: Token, blocks: Vec<(Token,Vec<Node>)>, redirs: Vec<Redir> },
 	IfThen { cond_blocks: Vec<(Vec<Node>,Vec<Node>)>, else_block: Option<Vec<Node>>, redirs: Vec<Redir> },
 	Loop { kind: LoopKind, cond: Vec<Node>, body: Vec<Node>, redirs: Vec<Redir> },
-	ForLoop { vars: Vec<Token>, arr: Vec<Token>, body: Vec<Node>, redirs: Vec<Redir> },
+	ForLoop { vars: Vec<Token>, arr: Option<Vec<Token>>, body: Vec<Node>, redirs: Vec<Redir> },
+	ArithFor { init: String, cond: String, step: String, body: Vec<Node>, redirs: Vec<Redir> },
 	Subshell { body: Token, argv: Vec<Token>, redirs: Vec<Redir> },
 	CmdList { cmds: Vec<(Option<CmdGuard>,Node)> },
 	Pipeline { cmds: Vec<Node> }
@@ -260,6 +261,29 @@ fn get_redir(token: Token, token_slice: &[Token], shenv: &mut ShEnv) -> ShResult
 	Ok((tokens_eaten,redir_bldr.build()))
 }
 
+/// Split the header of an arithmetic for loop on top-level semicolons
+fn split_arith_header(header: &str) -> Vec<String> {
+	let mut clauses = vec![];
+	let mut clause = String::new();
+	let mut paren_depth = 0;
+	for ch in header.chars() {
+		match ch {
+			'(' => {
+				paren_depth += 1;
+				clause.push(ch);
+			}
+			')' => {
+				paren_depth -= 1;
+				clause.push(ch);
+			}
+			';' if paren_depth == 0 => clauses.push(std::mem::take(&mut clause).trim().to_string()),
+			_ => clause.push(ch)
+		}
+	}
+	clauses.push(clause.trim().to_string());
+	clauses
+}
+
 // TODO: Redirs with FD sources appear to be looping endlessly for some reason
 
 ndrule_def!(Main, shenv, |tokens: &[Token], shenv: &mut ShEnv| {
@@ -533,6 +557,10 @@ ndrule_def!(ForLoop, shenv, |mut tokens: &[Token], shenv: &mut ShEnv| {
 	let mut vars = vec![];
 	let mut arr = vec![];
 	let mut redirs = vec![];
+	// Holds the (init; cond; step) clauses of a c-style for loop
+	let mut arith_clauses: Option<(String,String,String)> = None;
+	// `for name; do` and `for name do` iterate over the positional parameters
+	let mut use_pos_params = false;
 	let body: Vec<Node>;
 
 	if let Some(token) = tokens_iter.next() {
@@ -542,59 +570,90 @@ ndrule_def!(ForLoop, shenv, |mut tokens: &[Token], shenv: &mut ShEnv| {
 		} else { return Ok(None) }
 	} else { return Ok(None) }
 
-	while let Some(token) = tokens_iter.next() {
-		node_toks.push(token.clone());
-		tokens = &tokens[1..];
-		match token.rule() {
-			_ if token.as_raw(shenv) == "in" => break,
-			TkRule::Ident => {
-				vars.push(token.clone());
-			}
-			_ if KEYWORDS.contains(&token.rule()) => {
-				let mut clone = token.clone();
-				*clone.rule_mut() = TkRule::Ident;
-				vars.push(clone);
-			}
-			_ => {
-				log!(ERROR,"{:?}",token.rule());
-				log!(ERROR,token);
+	// The lexer sees `((init; cond; step))` as a subshell
+	if let Some(token) = tokens_iter.peek() {
+		let raw = token.as_raw(shenv);
+		if token.rule() == TkRule::Subshell && raw.starts_with("((") && raw.ends_with("))") {
+			let token = tokens_iter.next().unwrap();
+			node_toks.push(token.clone());
+			tokens = &tokens[1..];
+			let header = &raw[2..raw.len() - 2];
+			let clauses = split_arith_header(header);
+			if clauses.len() != 3 {
 				let span = get_span(&node_toks, shenv)?;
-				return Err(err("Expected an ident in for loop vars",span,shenv))
+				return Err(err("Expected three clauses in arithmetic for loop",span,shenv))
 			}
+			arith_clauses = Some((clauses[0].clone(),clauses[1].clone(),clauses[2].clone()));
 		}
 	}
-	if vars.is_empty() {
-		let span = get_span(&node_toks, shenv)?;
-		return Err(err("Expected an ident in for loop vars",span,shenv))
-	}
-	while let Some(token) = tokens_iter.next() {
-		node_toks.push(token.clone());
-		tokens = &tokens[1..];
-		match token.rule() {
-			TkRule::Sep => break,
-			TkRule::Ident => {
-				arr.push(token.clone());
-			}
-			_ if KEYWORDS.contains(&token.rule()) => {
-				let mut clone = token.clone();
-				*clone.rule_mut() = TkRule::Ident;
-				arr.push(clone);
-			}
-			_ => {
-				log!(ERROR,"{:?}",token.rule());
-				log!(ERROR,token);
-				let span = get_span(&node_toks, shenv)?;
-				return Err(err("Expected an ident in for loop array",span,shenv))
+
+	let mut closed = false;
+	if arith_clauses.is_none() {
+		while let Some(token) = tokens_iter.next() {
+			node_toks.push(token.clone());
+			tokens = &tokens[1..];
+			match token.rule() {
+				_ if token.as_raw(shenv) == "in" => break,
+				TkRule::Sep if !vars.is_empty() => {
+					use_pos_params = true;
+					break
+				}
+				// `do` is lexed as an ident in argument position
+				TkRule::Ident if !vars.is_empty() && token.as_raw(shenv) == "do" => {
+					use_pos_params = true;
+					closed = true;
+					break
+				}
+				TkRule::Ident => {
+					vars.push(token.clone());
+				}
+				_ if KEYWORDS.contains(&token.rule()) => {
+					let mut clone = token.clone();
+					*clone.rule_mut() = TkRule::Ident;
+					vars.push(clone);
+				}
+				_ => {
+					log!(ERROR,"{:?}",token.rule());
+					log!(ERROR,token);
+					let span = get_span(&node_toks, shenv)?;
+					return Err(err("Expected an ident in for loop vars",span,shenv))
+				}
 			}
 		}
+		if vars.is_empty() {
+			let span = get_span(&node_toks, shenv)?;
+			return Err(err("Expected an ident in for loop vars",span,shenv))
+		}
 	}
-	if arr.is_empty() {
-		log!(ERROR,node_toks);
-		let span = get_span(&node_toks, shenv)?;
-		return Err(err("Expected an ident in for loop array",span,shenv))
+	if arith_clauses.is_none() && !use_pos_params {
+		while let Some(token) = tokens_iter.next() {
+			node_toks.push(token.clone());
+			tokens = &tokens[1..];
+			match token.rule() {
+				TkRule::Sep => break,
+				TkRule::Ident => {
+					arr.push(token.clone());
+				}
+				_ if KEYWORDS.contains(&token.rule()) => {
+					let mut clone = token.clone();
+					*clone.rule_mut() = TkRule::Ident;
+					arr.push(clone);
+				}
+				_ => {
+					log!(ERROR,"{:?}",token.rule());
+					log!(ERROR,token);
+					let span = get_span(&node_toks, shenv)?;
+					return Err(err("Expected an ident in for loop array",span,shenv))
+				}
+			}
+		}
+		if arr.is_empty() {
+			log!(ERROR,node_toks);
+			let span = get_span(&node_toks, shenv)?;
+			return Err(err("Expected an ident in for loop array",span,shenv))
+		}
 	}
 
-	let mut closed = false;
 	while let Some(token) = tokens_iter.next() {
 		match token.rule() {
 			TkRule::Sep | TkRule::Whitespace => {
@@ -607,6 +666,12 @@ ndrule_def!(ForLoop, shenv, |mut tokens: &[Token], shenv: &mut ShEnv| {
 				tokens = &tokens[1..];
 				closed = true;
 			}
+			// `for ((...)) do` puts `do` in argument position
+			TkRule::Ident if !closed && token.as_raw(shenv) == "do" => {
+				node_toks.push(token.clone());
+				tokens = &tokens[1..];
+				closed = true;
+			}
 			_ => {
 				if closed { break }
 				let span = get_span(&node_toks,shenv)?;
@@ -664,8 +729,14 @@ ndrule_def!(ForLoop, shenv, |mut tokens: &[Token], shenv: &mut ShEnv| {
 	}
 
 	let span = get_span(&node_toks, shenv)?;
+	let node_rule = if let Some((init,cond,step)) = arith_clauses {
+		NdRule::ArithFor { init, cond, step, body, redirs }
+	} else {
+		let arr = if use_pos_params { None } else { Some(arr) };
+		NdRule::ForLoop { vars, arr, body, redirs }
+	};
 	let node = Node {
-		node_rule: NdRule::ForLoop { vars, arr, body, redirs },
+		node_rule,
 		tokens: node_toks,
 		span,
 		flags: NdFlag::empty()
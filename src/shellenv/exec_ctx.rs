@@ -6,6 +6,8 @@ bitflags! {
 		const NO_FORK   = 0b00000001;
 		const IN_FUNC   = 0b00000010;
 		const NO_EXPAND = 0b00000100;
+		const IN_SOURCE = 0b00001000;
+		const IN_SUBSH  = 0b00010000;
 	}
 }
 
@@ -14,6 +16,7 @@ pub struct ExecCtx {
 	redirs: Vec<Redir>,
 	depth: usize,
 	max_depth: usize,
+	loop_depth: usize,
 	flags: ExecFlags,
 	io_masks: IoMasks,
 	saved_io: Option<SavedIo>
@@ -25,6 +28,7 @@ impl ExecCtx {
 			redirs: vec![],
 			depth: 0,
 			max_depth: 1500,
+			loop_depth: 0,
 			flags: ExecFlags::empty(),
 			io_masks: IoMasks::new(),
 			saved_io: None
@@ -77,6 +81,16 @@ impl ExecCtx {
 		redirs.activate()?;
 		Ok(())
 	}
+	/// The number of loops enclosing the current command
+	pub fn loop_depth(&self) -> usize {
+		self.loop_depth
+	}
+	pub fn enter_loop(&mut self) {
+		self.loop_depth += 1
+	}
+	pub fn exit_loop(&mut self) {
+		self.loop_depth = self.loop_depth.saturating_sub(1)
+	}
 	pub fn flags(&self) -> ExecFlags {
 		self.flags
 	}
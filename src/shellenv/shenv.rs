@@ -39,7 +39,22 @@ impl ShEnv {
 			let mut buf = String::new();
 			file.read_to_string(&mut buf)?;
 
-			exec_input(buf, self)?;
+			// Sourcing replaces the current input, so the caller's input has to be put back afterward
+			let saved_input = self.input_man.clone();
+			let nested = self.ctx.flags().contains(ExecFlags::IN_SOURCE);
+			self.ctx.set_flag(ExecFlags::IN_SOURCE);
+			let result = exec_input(buf, self);
+			if !nested {
+				self.ctx.unset_flag(ExecFlags::IN_SOURCE);
+			}
+			self.input_man = saved_input;
+
+			match result {
+				Ok(()) => {}
+				// `return` in a sourced file stops sourcing that file
+				Err(e) if e.kind() == ShErrKind::FuncReturn => {}
+				Err(e) => return Err(e)
+			}
 		}
 		Ok(())
 	}
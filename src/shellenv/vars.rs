@@ -27,8 +27,8 @@ impl VarTab {
 		let mut params = HashMap::new();
 		let mut pos_params = VecDeque::new();
 
-		params.insert("@".to_string(), args.join(" "));
-		params.insert("#".to_string(), args.len().to_string());
+		params.insert("@".to_string(), args[1..].join(" "));
+		params.insert("#".to_string(), (args.len() - 1).to_string());
 
 		while let Some(arg) = args.pop() {
 			pos_params.fpush(arg);
@@ -108,6 +108,26 @@ impl VarTab {
 	pub fn reset_params(&mut self) {
 		self.params.clear();
 	}
+	/// Clear the positional parameters, keeping $0
+	pub fn reset_pos_params(&mut self) {
+		self.pos_params.truncate(1);
+		self.update_arg_params();
+	}
+	/// Replace all of the positional parameters, including $0
+	pub fn set_pos_params(&mut self, args: Vec<String>) {
+		self.pos_params = VecDeque::from(args);
+		self.update_arg_params();
+	}
+	/// Keep $@ and $# in sync with the positional parameters
+	fn update_arg_params(&mut self) {
+		let args = self.pos_args();
+		self.set_param("@", &args.join(" "));
+		self.set_param("#", &args.len().to_string());
+	}
+	/// Get the positional parameters, not including $0
+	pub fn pos_args(&self) -> Vec<String> {
+		self.pos_params.iter().skip(1).cloned().collect()
+	}
 	pub fn unset_param(&mut self, key: &str) {
 		self.params.remove(key);
 	}
@@ -120,27 +140,23 @@ impl VarTab {
 	/// Push an arg to the back of the positional parameter deque
 	pub fn bpush_arg(&mut self, arg: &str) {
 		self.pos_params.bpush(arg.to_string());
-		self.set_param("@", &self.pos_params.clone().to_vec().join(" "));
-		self.set_param("#", &self.pos_params.len().to_string());
+		self.update_arg_params();
 	}
 	/// Pop an arg from the back of the positional parameter deque
 	pub fn bpop_arg(&mut self) -> Option<String> {
 		let item = self.pos_params.bpop();
-		self.set_param("@", &self.pos_params.clone().to_vec().join(" "));
-		self.set_param("#", &self.pos_params.len().to_string());
+		self.update_arg_params();
 		item
 	}
 	/// Push an arg to the front of the positional parameter deque
 	pub fn fpush_arg(&mut self, arg: &str) {
 		self.pos_params.fpush(arg.to_string());
-		self.set_param("@", &self.pos_params.clone().to_vec().join(" "));
-		self.set_param("#", &self.pos_params.len().to_string());
+		self.update_arg_params();
 	}
 	/// Pop an arg from the front of the positional parameter deque
 	pub fn fpop_arg(&mut self) -> Option<String> {
 		let item = self.pos_params.fpop();
-		self.set_param("@", &self.pos_params.clone().to_vec().join(" "));
-		self.set_param("#", &self.pos_params.len().to_string());
+		self.update_arg_params();
 		item
 	}
 	pub fn get_var(&self, var: &str) -> &str {